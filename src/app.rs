@@ -1,8 +1,10 @@
 #![allow(unused_imports)]
 
 use egui::Image;
+use egui_dock::{DockArea, DockState, NodeIndex, Style, TabViewer};
 use poll_promise::Promise;
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
 use turbosql::{select, Turbosql};
 
 #[derive(Turbosql, Default)]
@@ -13,13 +15,23 @@ struct Card {
 	answer: Option<String>,
 	last_question_viewed_ms: Option<i64>,
 	last_answer_viewed_ms: Option<i64>,
+
+	/// SM-2 ease factor, initialized to 2.5 on a card's first review.
+	ease_factor: Option<f64>,
+	/// SM-2 inter-repetition interval, in days.
+	interval_days: Option<i64>,
+	/// Number of consecutive reviews graded `q >= 3`.
+	repetitions: Option<i64>,
+	/// When this card next becomes due for review.
+	due_ms: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize)]
 enum Action {
 	ViewedQuestion,
 	ViewedAnswer,
-	Responded { correct: bool },
+	/// SM-2 quality grade in `0..=5` (e.g. Again=0, Hard=3, Good=4, Easy=5).
+	Responded { q: u8 },
 }
 
 #[derive(Turbosql, Default)]
@@ -30,6 +42,51 @@ struct CardLog {
 	action: Option<Action>,
 }
 
+fn now_ms() -> i64 {
+	std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis() as i64
+}
+
+/// Applies the SM-2 algorithm to `card` for a review graded `q` (0..=5), updating its
+/// ease factor, interval, repetition count, and due date in place.
+fn schedule_review(card: &mut Card, q: i64) {
+	let ease_factor = card.ease_factor.get_or_insert(2.5);
+	let repetitions = card.repetitions.get_or_insert(0);
+	let interval_days = card.interval_days.get_or_insert(0);
+
+	if q < 3 {
+		*repetitions = 0;
+		*interval_days = 1;
+	} else {
+		*interval_days = match *repetitions {
+			0 => 1,
+			1 => 6,
+			_ => (*interval_days as f64 * *ease_factor).round() as i64,
+		};
+		*repetitions += 1;
+	}
+
+	*ease_factor = (*ease_factor + 0.1 - (5 - q) as f64 * (0.08 + (5 - q) as f64 * 0.02)).max(1.3);
+	card.due_ms = Some(now_ms() + *interval_days * 86_400_000);
+}
+
+/// Grades the card with rowid `card_id`, persists the new schedule, and logs the review.
+/// No-ops if no card has `card_id` (e.g. nothing is selected yet).
+fn respond(card_id: i64, q: i64) {
+	let Ok(mut card) = select!(Card "WHERE rowid = ?", card_id) else { return };
+	schedule_review(&mut card, q);
+	card.update().unwrap();
+
+	CardLog {
+		card_id: Some(card_id),
+		time_ms: Some(now_ms()),
+		action: Some(Action::Responded { q: q as u8 }),
+		..Default::default()
+	}
+	.insert()
+	.unwrap();
+}
+
+#[derive(Clone)]
 struct Resource {
 	/// HTTP response
 	response: ehttp::Response,
@@ -43,6 +100,24 @@ struct Resource {
 	colored_text: Option<ColoredText>,
 }
 
+/// A typed classification of a non-success HTTP response, so the UI can render something
+/// more useful than the raw body (e.g. an auth prompt for `NotAuthorized`).
+#[derive(Debug, Clone)]
+enum ResourceError {
+	NotFound { status: u16 },
+	NotAuthorized { status: u16 },
+	OpenRead { status: u16, body: String },
+	Server { status: u16, body: String },
+}
+
+/// Either a transport-level failure (the fetch API itself errored) or a successfully
+/// received response that turned out to carry an error status.
+#[derive(Debug, Clone)]
+enum FetchError {
+	Transport(String),
+	Resource(ResourceError),
+}
+
 impl Resource {
 	fn from_response(ctx: &egui::Context, response: ehttp::Response) -> Self {
 		let content_type = response.content_type().unwrap_or_default();
@@ -59,6 +134,331 @@ impl Resource {
 			Self { response, text, colored_text, image: None }
 		}
 	}
+
+	/// Like [`Self::from_response`], but classifies error statuses into a [`ResourceError`]
+	/// instead of rendering them as if they were a normal body.
+	fn try_from_response(
+		ctx: &egui::Context,
+		response: ehttp::Response,
+	) -> Result<Self, ResourceError> {
+		let status = response.status;
+
+		match status {
+			200..=299 => Ok(Self::from_response(ctx, response)),
+			404 => Err(ResourceError::NotFound { status }),
+			401 | 402 | 403 | 407 => Err(ResourceError::NotAuthorized { status }),
+			400..=499 => Err(ResourceError::OpenRead {
+				status,
+				body: response.text().unwrap_or_default().to_owned(),
+			}),
+			_ => {
+				Err(ResourceError::Server { status, body: response.text().unwrap_or_default().to_owned() })
+			}
+		}
+	}
+}
+
+// ----------------------------------------------------------------------------
+// Chunked, resumable downloads:
+
+/// Bytes requested per range chunk.
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// Maximum number of consecutive retries of a failed chunk before the download gives up.
+const MAX_RETRIES: u32 = 3;
+
+/// Download progress, polled by the UI each frame to drive a progress bar.
+#[derive(Clone, Default)]
+struct DownloadProgress {
+	received: usize,
+	total: Option<usize>,
+}
+
+struct DownloadState {
+	url: String,
+	buffer: Vec<u8>,
+	/// The first response received, kept as a template for headers/status once the
+	/// download completes.
+	template: Option<ehttp::Response>,
+	retries: u32,
+}
+
+/// Parses the total size out of a `Content-Range: bytes start-end/total` header.
+fn parse_content_range_total(response: &ehttp::Response) -> Option<usize> {
+	let content_range = response.headers.get("content-range")?;
+	content_range.rsplit('/').next()?.parse().ok()
+}
+
+fn finish_download(
+	ctx: &egui::Context,
+	state: &Arc<Mutex<DownloadState>>,
+	sender: Arc<Mutex<Option<poll_promise::Sender<Result<Resource, FetchError>>>>>,
+	cache: &Arc<Mutex<ImageCache>>,
+) {
+	let Some(sender) = sender.lock().unwrap().take() else { return };
+	let mut state = state.lock().unwrap();
+	let mut response = state.template.take().expect("a chunk must have been received");
+
+	if response.status < 400 {
+		// Only the reassembled success path needs its status/body synthesized; an error
+		// response forwarded here via `finish_download_with` already carries its real
+		// status, status_text, and body, which `try_from_response` needs to classify it.
+		response.status = 200;
+		response.status_text = "OK".to_owned();
+		response.bytes = std::mem::take(&mut state.buffer);
+	}
+
+	let resource = Resource::try_from_response(ctx, response).map_err(FetchError::Resource);
+	cache.lock().unwrap().insert(ctx, &state.url, resource.clone());
+	sender.send(resource);
+}
+
+/// Fetches `state`'s URL one `CHUNK_SIZE` range at a time, resuming from `state.buffer.len()`,
+/// and falls back to treating a plain `200` response as the whole body when the server
+/// doesn't honor the `Range` header. Retries a failed chunk up to [`MAX_RETRIES`] times,
+/// each retry resuming from the last received offset. After that it reports the transport
+/// error and the partial buffer is dropped with `state`; a later download of the same URL
+/// starts again from the first byte.
+fn fetch_next_chunk(
+	ctx: egui::Context,
+	state: Arc<Mutex<DownloadState>>,
+	progress: Arc<Mutex<DownloadProgress>>,
+	sender: Arc<Mutex<Option<poll_promise::Sender<Result<Resource, FetchError>>>>>,
+	cache: Arc<Mutex<ImageCache>>,
+) {
+	let (url, offset) = {
+		let state = state.lock().unwrap();
+		(state.url.clone(), state.buffer.len())
+	};
+
+	let mut request = ehttp::Request::get(&url);
+	request
+		.headers
+		.insert("Range".to_owned(), format!("bytes={}-{}", offset, offset + CHUNK_SIZE - 1));
+
+	ehttp::fetch(request, move |response| {
+		ctx.request_repaint();
+
+		let response = match response {
+			Ok(response) => response,
+			Err(error) => {
+				let mut state_guard = state.lock().unwrap();
+				state_guard.retries += 1;
+				if state_guard.retries > MAX_RETRIES {
+					drop(state_guard);
+					if let Some(sender) = sender.lock().unwrap().take() {
+						sender.send(Err(FetchError::Transport(error)));
+					}
+				} else {
+					drop(state_guard);
+					fetch_next_chunk(ctx, state, progress, sender, cache);
+				}
+				return;
+			}
+		};
+
+		if response.status >= 400 {
+			// Let the normal status classification in `try_from_response` handle it.
+			finish_download_with(&ctx, &state, &sender, &cache, response);
+			return;
+		}
+
+		let done = {
+			let mut state_guard = state.lock().unwrap();
+			state_guard.retries = 0;
+			if state_guard.template.is_none() {
+				state_guard.template = Some(response.clone());
+			}
+
+			match response.status {
+				206 => state_guard.buffer.extend_from_slice(&response.bytes),
+				// The server ignored our `Range` header: this is the whole body.
+				_ => state_guard.buffer = response.bytes.clone(),
+			}
+
+			// The "whole body" fallback is only valid when the server ignored our `Range`
+			// header: on a genuine `206`, an unparseable `Content-Range` just means we don't
+			// know the total yet, not that `buffer` already holds it.
+			let total = parse_content_range_total(&response)
+				.or((response.status != 206).then(|| state_guard.buffer.len()));
+			let mut progress_guard = progress.lock().unwrap();
+			progress_guard.received = state_guard.buffer.len();
+			progress_guard.total = total;
+
+			response.status != 206 || total == Some(state_guard.buffer.len())
+		};
+
+		if done {
+			finish_download(&ctx, &state, sender, &cache);
+		} else {
+			fetch_next_chunk(ctx, state, progress, sender, cache);
+		}
+	});
+}
+
+fn finish_download_with(
+	ctx: &egui::Context,
+	state: &Arc<Mutex<DownloadState>>,
+	sender: &Arc<Mutex<Option<poll_promise::Sender<Result<Resource, FetchError>>>>>,
+	cache: &Arc<Mutex<ImageCache>>,
+	response: ehttp::Response,
+) {
+	state.lock().unwrap().template = Some(response);
+	finish_download(ctx, state, sender.clone(), cache);
+}
+
+/// Kicks off a resumable, chunked download of `url`, reporting progress via `progress`,
+/// inserting the final result into `cache`, and delivering it through `sender`. The cache is
+/// not consulted here; callers check it before starting a download.
+fn start_download(
+	ctx: egui::Context,
+	url: String,
+	progress: Arc<Mutex<DownloadProgress>>,
+	sender: poll_promise::Sender<Result<Resource, FetchError>>,
+	cache: Arc<Mutex<ImageCache>>,
+) {
+	let state =
+		Arc::new(Mutex::new(DownloadState { url, buffer: Vec::new(), template: None, retries: 0 }));
+	let sender = Arc::new(Mutex::new(Some(sender)));
+	fetch_next_chunk(ctx, state, progress, sender, cache);
+}
+
+// ----------------------------------------------------------------------------
+// URL-keyed resource cache:
+
+/// Default byte budget for [`ImageCache`] — enough to hold a few dozen typical images.
+const IMAGE_CACHE_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+
+/// An LRU cache of fetched resources keyed by a hash of their URL, so revisiting a URL
+/// (e.g. via back/forward navigation) reuses the decoded `Resource` instead of re-fetching
+/// and re-decoding it. Evicts least-recently-used entries once `budget_bytes` is exceeded.
+struct ImageCache {
+	entries: std::collections::HashMap<u64, Result<Resource, FetchError>>,
+	/// Least-recently-used key at the front, most-recently-used at the back.
+	lru: std::collections::VecDeque<u64>,
+	total_bytes: usize,
+	budget_bytes: usize,
+}
+
+impl ImageCache {
+	fn new(budget_bytes: usize) -> Self {
+		Self { entries: Default::default(), lru: Default::default(), total_bytes: 0, budget_bytes }
+	}
+
+	fn hash_url(url: &str) -> u64 {
+		use std::hash::{Hash, Hasher};
+		let mut hasher = std::collections::hash_map::DefaultHasher::new();
+		url.hash(&mut hasher);
+		hasher.finish()
+	}
+
+	fn byte_size(resource: &Result<Resource, FetchError>) -> usize {
+		resource.as_ref().map(|resource| resource.response.bytes.len()).unwrap_or(0)
+	}
+
+	fn contains(&self, url: &str) -> bool {
+		self.entries.contains_key(&Self::hash_url(url))
+	}
+
+	fn get(&mut self, url: &str) -> Option<Result<Resource, FetchError>> {
+		let key = Self::hash_url(url);
+		let resource = self.entries.get(&key)?.clone();
+		self.lru.retain(|&k| k != key);
+		self.lru.push_back(key);
+		Some(resource)
+	}
+
+	/// Inserts `resource` under `url`, evicting least-recently-used entries (and forgetting
+	/// their registered image bytes via `ctx`) until back under budget.
+	fn insert(&mut self, ctx: &egui::Context, url: &str, resource: Result<Resource, FetchError>) {
+		// Transport failures are transient; retrying is cheap, so don't cache them.
+		if matches!(resource, Err(FetchError::Transport(_))) {
+			return;
+		}
+
+		let key = Self::hash_url(url);
+		if let Some(old) = self.entries.remove(&key) {
+			self.total_bytes -= Self::byte_size(&old);
+		}
+		self.lru.retain(|&k| k != key);
+
+		self.total_bytes += Self::byte_size(&resource);
+		self.entries.insert(key, resource);
+		self.lru.push_back(key);
+
+		while self.total_bytes > self.budget_bytes {
+			let Some(oldest) = self.lru.pop_front() else { break };
+			if let Some(evicted) = self.entries.remove(&oldest) {
+				self.total_bytes -= Self::byte_size(&evicted);
+				if let Ok(resource) = &evicted {
+					ctx.forget_image(&resource.response.url);
+				}
+			}
+		}
+	}
+}
+
+// ----------------------------------------------------------------------------
+// Export rendered content as a PNG:
+
+/// Records that the next `egui::Event::Screenshot` should be cropped to `rect` and
+/// resized by `scale` before being written out.
+struct PendingScreenshot {
+	rect: egui::Rect,
+	scale: f32,
+}
+
+/// Crops `image` (full-viewport pixels, in physical pixels) down to `pending.rect`
+/// (in points), scales it by `pending.scale`, and writes it out as a PNG.
+fn export_screenshot(image: &egui::ColorImage, pending: &PendingScreenshot, pixels_per_point: f32) {
+	let [full_width, full_height] = image.size;
+
+	let x0 = ((pending.rect.min.x * pixels_per_point).round() as i64).clamp(0, full_width as i64) as usize;
+	let y0 = ((pending.rect.min.y * pixels_per_point).round() as i64).clamp(0, full_height as i64) as usize;
+	let x1 = ((pending.rect.max.x * pixels_per_point).round() as i64).clamp(0, full_width as i64) as usize;
+	let y1 = ((pending.rect.max.y * pixels_per_point).round() as i64).clamp(0, full_height as i64) as usize;
+
+	let (crop_width, crop_height) = (x1.saturating_sub(x0), y1.saturating_sub(y0));
+	if crop_width == 0 || crop_height == 0 {
+		return;
+	}
+
+	let mut pixels = Vec::with_capacity(crop_width * crop_height * 4);
+	for y in y0..y1 {
+		let row_start = y * full_width;
+		for x in x0..x1 {
+			pixels.extend_from_slice(&image.pixels[row_start + x].to_array());
+		}
+	}
+
+	let Some(cropped) = image::RgbaImage::from_raw(crop_width as u32, crop_height as u32, pixels) else {
+		return;
+	};
+
+	let scaled = if pending.scale == 1.0 {
+		cropped
+	} else {
+		image::imageops::resize(
+			&cropped,
+			(crop_width as f32 * pending.scale).round() as u32,
+			(crop_height as f32 * pending.scale).round() as u32,
+			image::imageops::FilterType::Lanczos3,
+		)
+	};
+
+	let path = format!("export-{}.png", now_ms());
+	if let Err(error) = scaled.save(&path) {
+		eprintln!("failed to export screenshot to {path}: {error}");
+	}
+}
+
+/// A single pane of the dockable workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum Tab {
+	Cards,
+	Http,
+	Headers,
+	Review,
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -66,16 +466,57 @@ pub struct HttpApp {
 	url: String,
 	line_selected: i64,
 
+	/// Visited URLs, oldest first, persisted across restarts. `history_cursor` points at the
+	/// currently displayed one.
+	history: Vec<String>,
+	history_cursor: usize,
+
+	/// The user's current arrangement of tabs, persisted across restarts.
+	dock_state: DockState<Tab>,
+
+	#[cfg_attr(feature = "serde", serde(skip))]
+	promise: Option<Promise<Result<Resource, FetchError>>>,
+
 	#[cfg_attr(feature = "serde", serde(skip))]
-	promise: Option<Promise<ehttp::Result<Resource>>>,
+	download_progress: Option<Arc<Mutex<DownloadProgress>>>,
+
+	#[cfg_attr(feature = "serde", serde(skip, default = "default_image_cache"))]
+	image_cache: Arc<Mutex<ImageCache>>,
+
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pending_screenshot: Option<PendingScreenshot>,
+
+	#[cfg_attr(feature = "serde", serde(skip))]
+	export_scale: f32,
+}
+
+/// Default value for [`HttpApp::image_cache`] when deserializing persisted state, which
+/// skips this field (it holds live egui registrations, not just data).
+fn default_image_cache() -> Arc<Mutex<ImageCache>> {
+	Arc::new(Mutex::new(ImageCache::new(IMAGE_CACHE_BUDGET_BYTES)))
 }
 
 impl Default for HttpApp {
 	fn default() -> Self {
+		let url = "https://raw.githubusercontent.com/emilk/egui/master/README.md".to_owned();
+
+		let mut dock_state = DockState::new(vec![Tab::Http]);
+		let surface = dock_state.main_surface_mut();
+		let [http, _headers] = surface.split_below(NodeIndex::root(), 0.75, vec![Tab::Headers]);
+		let [_, cards] = surface.split_left(http, 0.25, vec![Tab::Cards]);
+		let _ = surface.split_below(cards, 0.6, vec![Tab::Review]);
+
 		Self {
-			url: "https://raw.githubusercontent.com/emilk/egui/master/README.md".to_owned(),
+			history: vec![url.clone()],
+			history_cursor: 0,
+			url,
 			line_selected: Default::default(),
+			dock_state,
 			promise: Default::default(),
+			download_progress: Default::default(),
+			image_cache: default_image_cache(),
+			pending_screenshot: Default::default(),
+			export_scale: 2.0,
 		}
 	}
 }
@@ -86,89 +527,349 @@ impl HttpApp {
 
 		egui_extras::install_image_loaders(&cc.egui_ctx);
 
-		// Load previous app state (if any).
-		// Note that you must enable the `persistence` feature for this to work.
-		// if let Some(storage) = cc.storage {
-		//     return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
-		// }
+		// Load previous app state (if any). Requires the `serde` feature.
+		#[cfg(feature = "serde")]
+		if let Some(storage) = cc.storage {
+			if let Some(app) = eframe::get_value(storage, eframe::APP_KEY) {
+				return app;
+			}
+		}
 
 		Self::default()
 	}
 }
 
-impl eframe::App for HttpApp {
-	fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
-		ctx.input(|i| {
-			if i.key_pressed(egui::Key::ArrowDown) {
-				self.line_selected += 1;
-			} else if i.key_pressed(egui::Key::ArrowUp) {
-				self.line_selected -= 1;
-			} else if i.key_pressed(egui::Key::Enter) {
-				Card::default().insert().unwrap();
-			}
-		});
+/// Navigates to `url`, truncating any forward history and pushing it as the new entry.
+/// Returns `false` (without touching history) if `url` is already the current entry.
+fn history_navigate_to(
+	history: &mut Vec<String>,
+	cursor: &mut usize,
+	url: &mut String,
+	new_url: String,
+) -> bool {
+	if history.get(*cursor) == Some(&new_url) {
+		return false;
+	}
+	history.truncate(*cursor + 1);
+	history.push(new_url.clone());
+	*cursor = history.len() - 1;
+	*url = new_url;
+	true
+}
+
+/// Moves the history cursor back one entry. Returns `false` if already at the start.
+fn history_go_back(history: &[String], cursor: &mut usize, url: &mut String) -> bool {
+	let Some(new_cursor) = cursor.checked_sub(1) else { return false };
+	*cursor = new_cursor;
+	*url = history[new_cursor].clone();
+	true
+}
 
-		let cards = select!(Vec<Card>).unwrap();
+/// Moves the history cursor forward one entry. Returns `false` if already at the end.
+fn history_go_forward(history: &[String], cursor: &mut usize, url: &mut String) -> bool {
+	let new_cursor = *cursor + 1;
+	if new_cursor >= history.len() {
+		return false;
+	}
+	*cursor = new_cursor;
+	*url = history[new_cursor].clone();
+	true
+}
 
-		egui::SidePanel::left("left_panel").show(ctx, |ui| {
-			egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
-				for card in cards {
-					let i = card.rowid.unwrap();
-					if ui.selectable_label(i == self.line_selected, format!("Card {}", i)).clicked() {
-						self.line_selected = i;
-					}
+/// Borrows the pieces of [`HttpApp`] each tab needs, so [`egui_dock::DockArea::show`] can
+/// hand out mutable access to the right fields without borrowing the whole app.
+struct HttpTabViewer<'a> {
+	ctx: egui::Context,
+	frame: &'a mut eframe::Frame,
+	url: &'a mut String,
+	history: &'a mut Vec<String>,
+	history_cursor: &'a mut usize,
+	line_selected: &'a mut i64,
+	promise: &'a mut Option<Promise<Result<Resource, FetchError>>>,
+	download_progress: &'a mut Option<Arc<Mutex<DownloadProgress>>>,
+	image_cache: &'a Arc<Mutex<ImageCache>>,
+	pending_screenshot: &'a mut Option<PendingScreenshot>,
+	export_scale: &'a mut f32,
+	nav_back: bool,
+	nav_forward: bool,
+}
+
+impl HttpTabViewer<'_> {
+	fn ui_cards(&mut self, ui: &mut egui::Ui) {
+		let now = now_ms();
+		let due_cards = select!(Vec<Card> "WHERE due_ms <= ? ORDER BY due_ms", now).unwrap();
+		egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+			for card in due_cards {
+				let i = card.rowid.unwrap();
+				if ui.selectable_label(i == *self.line_selected, format!("Card {}", i)).clicked() {
+					*self.line_selected = i;
 				}
-			});
+			}
 		});
+	}
 
-		egui::CentralPanel::default().show(ctx, |ui| {
-			let prev_url = self.url.clone();
-			let trigger_fetch = ui_url(ui, frame, &mut self.url);
+	fn ui_http(&mut self, ui: &mut egui::Ui) {
+		let prev_url = self.url.clone();
+
+		let can_go_back = *self.history_cursor > 0;
+		let can_go_forward = *self.history_cursor + 1 < self.history.len();
+		let mut trigger_fetch = match ui_url(ui, self.frame, self.url, can_go_back, can_go_forward) {
+			UrlAction::None => false,
+			UrlAction::Navigate => {
+				let url = self.url.clone();
+				history_navigate_to(self.history, self.history_cursor, self.url, url)
+			}
+			UrlAction::Back => history_go_back(self.history, self.history_cursor, self.url),
+			UrlAction::Forward => history_go_forward(self.history, self.history_cursor, self.url),
+		};
+
+		if self.nav_back {
+			trigger_fetch |= history_go_back(self.history, self.history_cursor, self.url);
+		} else if self.nav_forward {
+			trigger_fetch |= history_go_forward(self.history, self.history_cursor, self.url);
+		}
+
+		if trigger_fetch {
+			// Only forget the previous image if it's no longer in `image_cache` — if it's
+			// still cached, a later cache-hit navigation back to it needs those bytes to
+			// still be registered with egui's image loader.
+			if !self.image_cache.lock().unwrap().contains(&prev_url) {
+				self.ctx.forget_image(&prev_url);
+			}
 
-			if trigger_fetch {
-				let ctx = ctx.clone();
+			if let Some(cached) = self.image_cache.lock().unwrap().get(self.url.as_str()) {
+				*self.download_progress = None;
+				*self.promise = Some(Promise::from_ready(cached));
+			} else {
+				let progress = Arc::new(Mutex::new(DownloadProgress::default()));
 				let (sender, promise) = Promise::new();
-				let request = ehttp::Request::get(&self.url);
-				ehttp::fetch(request, move |response| {
-					ctx.forget_image(&prev_url);
-					ctx.request_repaint(); // wake up UI thread
-					let resource = response.map(|response| Resource::from_response(&ctx, response));
-					sender.send(resource);
-				});
-				self.promise = Some(promise);
+				start_download(
+					self.ctx.clone(),
+					self.url.clone(),
+					progress.clone(),
+					sender,
+					self.image_cache.clone(),
+				);
+				*self.download_progress = Some(progress);
+				*self.promise = Some(promise);
 			}
+		}
 
-			ui.label(format!("Selected line: {}", self.line_selected));
+		ui.label(format!("Selected line: {}", *self.line_selected));
 
-			ui.separator();
+		ui.separator();
 
-			if let Some(promise) = &self.promise {
-				if let Some(result) = promise.ready() {
-					match result {
-						Ok(resource) => {
-							ui_resource(ui, resource);
-						}
-						Err(error) => {
-							// This should only happen if the fetch API isn't available or something similar.
-							ui
-								.colored_label(ui.visuals().error_fg_color, if error.is_empty() { "Error" } else { error });
-						}
+		if let Some(promise) = &self.promise {
+			if let Some(result) = promise.ready() {
+				match result {
+					Ok(resource) => {
+						let content = ui.scope(|ui| ui_resource(ui, resource)).response;
+
+						ui.separator();
+						ui.horizontal(|ui| {
+							ui.add(egui::Slider::new(self.export_scale, 1.0..=4.0).text("export scale"));
+							if ui.button("🖼 Export as image").clicked() {
+								*self.pending_screenshot =
+									Some(PendingScreenshot { rect: content.rect, scale: *self.export_scale });
+								self.ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(Default::default()));
+							}
+						});
+					}
+					Err(FetchError::Transport(error)) => {
+						// This should only happen if the fetch API isn't available or something similar.
+						ui
+							.colored_label(ui.visuals().error_fg_color, if error.is_empty() { "Error" } else { error });
+					}
+					Err(FetchError::Resource(resource_error)) => {
+						ui_resource_error(ui, resource_error);
+					}
+				}
+			} else if let Some(progress) = &self.download_progress {
+				let progress = progress.lock().unwrap().clone();
+				match progress.total {
+					Some(total) if total > 0 => {
+						let fraction = progress.received as f32 / total as f32;
+						ui.add(
+							egui::ProgressBar::new(fraction)
+								.text(format!("{} / {} kB", progress.received / 1000, total / 1000)),
+						);
+					}
+					_ => {
+						ui.spinner();
 					}
-				} else {
-					ui.spinner();
 				}
 			}
+		}
+	}
+
+	fn ui_headers(&mut self, ui: &mut egui::Ui) {
+		match self.promise.as_ref().and_then(|promise| promise.ready()) {
+			Some(Ok(resource)) => ui_response_headers(ui, &resource.response),
+			_ => {
+				ui.weak("No response yet.");
+			}
+		}
+	}
+
+	fn ui_review(&mut self, ui: &mut egui::Ui) {
+		let Ok(card) = select!(Card "WHERE rowid = ?", *self.line_selected) else {
+			ui.weak("Select a card to review it.");
+			return;
+		};
+
+		if let Some(question) = &card.question {
+			ui.label(question);
+		}
+		ui.separator();
+		if let Some(answer) = &card.answer {
+			ui.label(answer);
+		}
+
+		ui.separator();
+		ui.horizontal(|ui| {
+			if ui.button("Again").clicked() {
+				respond(*self.line_selected, 0);
+			}
+			if ui.button("Hard").clicked() {
+				respond(*self.line_selected, 3);
+			}
+			if ui.button("Good").clicked() {
+				respond(*self.line_selected, 4);
+			}
+			if ui.button("Easy").clicked() {
+				respond(*self.line_selected, 5);
+			}
+		});
+	}
+}
+
+impl TabViewer for HttpTabViewer<'_> {
+	type Tab = Tab;
+
+	fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
+		match tab {
+			Tab::Cards => "Cards".into(),
+			Tab::Http => "HTTP".into(),
+			Tab::Headers => "Response headers".into(),
+			Tab::Review => "Review".into(),
+		}
+	}
+
+	fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
+		match tab {
+			Tab::Cards => self.ui_cards(ui),
+			Tab::Http => self.ui_http(ui),
+			Tab::Headers => self.ui_headers(ui),
+			Tab::Review => self.ui_review(ui),
+		}
+	}
+}
+
+impl eframe::App for HttpApp {
+	fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+		let mut nav_back = false;
+		let mut nav_forward = false;
+
+		ctx.input(|i| {
+			if i.key_pressed(egui::Key::ArrowDown) {
+				self.line_selected += 1;
+			} else if i.key_pressed(egui::Key::ArrowUp) {
+				self.line_selected -= 1;
+			} else if i.key_pressed(egui::Key::Enter) {
+				Card { due_ms: Some(now_ms()), ..Default::default() }.insert().unwrap();
+			} else if i.key_pressed(egui::Key::Num1) {
+				respond(self.line_selected, 0); // Again
+			} else if i.key_pressed(egui::Key::Num2) {
+				respond(self.line_selected, 3); // Hard
+			} else if i.key_pressed(egui::Key::Num3) {
+				respond(self.line_selected, 4); // Good
+			} else if i.key_pressed(egui::Key::Num4) {
+				respond(self.line_selected, 5); // Easy
+			} else if i.modifiers.alt && i.key_pressed(egui::Key::ArrowLeft) {
+				nav_back = true;
+			} else if i.modifiers.alt && i.key_pressed(egui::Key::ArrowRight) {
+				nav_forward = true;
+			}
 		});
+
+		if let Some(pending) = &self.pending_screenshot {
+			let screenshot = ctx.input(|i| {
+				i.events.iter().find_map(|event| match event {
+					egui::Event::Screenshot { image, .. } => Some(image.clone()),
+					_ => None,
+				})
+			});
+			if let Some(image) = screenshot {
+				export_screenshot(&image, pending, ctx.pixels_per_point());
+				self.pending_screenshot = None;
+			}
+		}
+
+		let mut viewer = HttpTabViewer {
+			ctx: ctx.clone(),
+			frame,
+			url: &mut self.url,
+			history: &mut self.history,
+			history_cursor: &mut self.history_cursor,
+			line_selected: &mut self.line_selected,
+			promise: &mut self.promise,
+			download_progress: &mut self.download_progress,
+			image_cache: &self.image_cache,
+			pending_screenshot: &mut self.pending_screenshot,
+			export_scale: &mut self.export_scale,
+			nav_back,
+			nav_forward,
+		};
+
+		DockArea::new(&mut self.dock_state)
+			.style(Style::from_egui(ctx.style().as_ref()))
+			.show(ctx, &mut viewer);
 	}
+
+	/// Persists `history`/`dock_state` (and everything else that isn't `serde(skip)`-ed)
+	/// so they're restored by `HttpApp::new` on the next launch. Requires the `serde` feature.
+	#[cfg(feature = "serde")]
+	fn save(&mut self, storage: &mut dyn eframe::Storage) {
+		eframe::set_value(storage, eframe::APP_KEY, self);
+	}
+}
+
+/// What the URL bar wants the app to do this frame.
+enum UrlAction {
+	None,
+	/// The URL field lost focus (e.g. after an edit) or a shortcut (e.g. "Random image") set
+	/// `url` to a new value.
+	Navigate,
+	Back,
+	Forward,
 }
 
-fn ui_url(ui: &mut egui::Ui, _frame: &mut eframe::Frame, url: &mut String) -> bool {
-	let mut trigger_fetch = false;
+fn ui_url(
+	ui: &mut egui::Ui,
+	_frame: &mut eframe::Frame,
+	url: &mut String,
+	can_go_back: bool,
+	can_go_forward: bool,
+) -> UrlAction {
+	let mut action = UrlAction::None;
 
 	ui.horizontal(|ui| {
+		if ui.add_enabled(can_go_back, egui::Button::new("⬅")).on_hover_text("Back (Alt+Left)").clicked()
+		{
+			action = UrlAction::Back;
+		}
+		if ui
+			.add_enabled(can_go_forward, egui::Button::new("➡"))
+			.on_hover_text("Forward (Alt+Right)")
+			.clicked()
+		{
+			action = UrlAction::Forward;
+		}
+
 		ui.label("URL:");
-		trigger_fetch |=
-			ui.add(egui::TextEdit::singleline(url).desired_width(f32::INFINITY)).lost_focus();
+		if ui.add(egui::TextEdit::singleline(url).desired_width(f32::INFINITY)).lost_focus() {
+			action = UrlAction::Navigate;
+		}
 	});
 
 	ui.horizontal(|ui| {
@@ -176,11 +877,26 @@ fn ui_url(ui: &mut egui::Ui, _frame: &mut eframe::Frame, url: &mut String) -> bo
 			let seed = ui.input(|i| i.time);
 			let side = 640;
 			*url = format!("https://picsum.photos/seed/{seed}/{side}");
-			trigger_fetch = true;
+			action = UrlAction::Navigate;
 		}
 	});
 
-	trigger_fetch
+	action
+}
+
+/// Renders the response-headers grid, used by the dedicated "Response headers" tab.
+fn ui_response_headers(ui: &mut egui::Ui, response: &ehttp::Response) {
+	egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+		egui::Grid::new("response_headers")
+			.spacing(egui::vec2(ui.spacing().item_spacing.x * 2.0, 0.0))
+			.show(ui, |ui| {
+				for header in &response.headers {
+					ui.label(header.0);
+					ui.label(header.1);
+					ui.end_row();
+				}
+			})
+	});
 }
 
 fn ui_resource(ui: &mut egui::Ui, resource: &Resource) {
@@ -194,20 +910,6 @@ fn ui_resource(ui: &mut egui::Ui, resource: &Resource) {
 	ui.separator();
 
 	egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
-		egui::CollapsingHeader::new("Response headers").default_open(false).show(ui, |ui| {
-			egui::Grid::new("response_headers")
-				.spacing(egui::vec2(ui.spacing().item_spacing.x * 2.0, 0.0))
-				.show(ui, |ui| {
-					for header in &response.headers {
-						ui.label(header.0);
-						ui.label(header.1);
-						ui.end_row();
-					}
-				})
-		});
-
-		ui.separator();
-
 		if let Some(text) = &text {
 			let tooltip = "Click to copy the response body";
 			if ui.button("📋").on_hover_text(tooltip).clicked() {
@@ -228,6 +930,30 @@ fn ui_resource(ui: &mut egui::Ui, resource: &Resource) {
 	});
 }
 
+fn ui_resource_error(ui: &mut egui::Ui, error: &ResourceError) {
+	match error {
+		ResourceError::NotFound { status } => {
+			ui.colored_label(ui.visuals().error_fg_color, format!("Not found ({status})"));
+		}
+		ResourceError::NotAuthorized { status } => {
+			ui.colored_label(ui.visuals().warn_fg_color, format!("Not authorized ({status})"));
+			ui.label("This resource requires credentials.");
+		}
+		ResourceError::OpenRead { status, body } => {
+			ui.colored_label(ui.visuals().error_fg_color, format!("Request failed ({status})"));
+			if !body.is_empty() {
+				selectable_text(ui, body);
+			}
+		}
+		ResourceError::Server { status, body } => {
+			ui.colored_label(ui.visuals().error_fg_color, format!("Server error ({status})"));
+			if !body.is_empty() {
+				selectable_text(ui, body);
+			}
+		}
+	}
+}
+
 fn selectable_text(ui: &mut egui::Ui, mut text: &str) {
 	ui.add(
 		egui::TextEdit::multiline(&mut text)
@@ -250,6 +976,7 @@ fn syntax_highlighting(
 	Some(ColoredText(egui_extras::syntax_highlighting::highlight(ctx, &theme, text, extension)))
 }
 
+#[derive(Clone)]
 struct ColoredText(egui::text::LayoutJob);
 
 impl ColoredText {